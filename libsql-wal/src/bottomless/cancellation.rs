@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+
+use tokio::sync::Notify;
+
+use libsql_sys::name::NamespaceName;
+
+/// A node in a tree of cancellation tokens.
+///
+/// Cancelling a token also cancels all of its live children, recursively, but never its
+/// parent. This lets [`Bottomless`](super::Bottomless) hold a single root token for the whole
+/// loop, while handing out a child token per namespace so that cancelling one namespace's
+/// in-flight durability work doesn't affect the others, or the loop itself.
+struct Node {
+    state: Mutex<State>,
+    notify: Notify,
+}
+
+struct State {
+    parent: Option<Arc<Node>>,
+    children: Vec<Weak<Node>>,
+    cancelled: bool,
+}
+
+#[derive(Clone)]
+pub struct CancellationToken {
+    node: Arc<Node>,
+}
+
+impl CancellationToken {
+    /// Creates a new, unlinked root token.
+    pub fn new() -> Self {
+        Self {
+            node: Arc::new(Node {
+                state: Mutex::new(State {
+                    parent: None,
+                    children: Vec::new(),
+                    cancelled: false,
+                }),
+                notify: Notify::new(),
+            }),
+        }
+    }
+
+    /// Derives a new token that is cancelled whenever `self` is cancelled, but can also be
+    /// cancelled independently without affecting `self` or its other children.
+    pub fn child_token(&self) -> Self {
+        let child = Arc::new(Node {
+            state: Mutex::new(State {
+                parent: Some(self.node.clone()),
+                children: Vec::new(),
+                cancelled: false,
+            }),
+            notify: Notify::new(),
+        });
+
+        let mut state = self.node.state.lock().unwrap();
+        if state.cancelled {
+            // the parent is already cancelled: no point registering, just propagate immediately.
+            drop(state);
+            child.cancel();
+        } else {
+            prune_dead_children(&mut state.children);
+            state.children.push(Arc::downgrade(&child));
+        }
+
+        Self { node: child }
+    }
+
+    /// Cancels this token and all of its live children.
+    pub fn cancel(&self) {
+        self.node.cancel();
+    }
+
+    /// Returns `true` if this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.node.state.lock().unwrap().cancelled
+    }
+
+    /// Resolves as soon as this token is cancelled, immediately if it already is.
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.node.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+            if self.is_cancelled() {
+                return;
+            }
+        }
+    }
+}
+
+impl Node {
+    fn cancel(self: &Arc<Self>) {
+        let children = {
+            let mut state = self.state.lock().unwrap();
+            if state.cancelled {
+                return;
+            }
+            state.cancelled = true;
+            std::mem::take(&mut state.children)
+        };
+
+        self.notify.notify_waiters();
+
+        for child in children {
+            if let Some(child) = child.upgrade() {
+                child.cancel();
+            }
+        }
+    }
+}
+
+fn prune_dead_children(children: &mut Vec<Weak<Node>>) {
+    children.retain(|child| child.strong_count() > 0);
+}
+
+/// Tracks the per-namespace child token derived from a single root, so that a namespace's
+/// in-flight store jobs can be cancelled (e.g. when its database is dropped or migrated)
+/// without tearing down the whole [`BottomlessLoop`](super::BottomlessLoop).
+#[derive(Clone, Default)]
+pub(super) struct NamespaceTokens {
+    tokens: Arc<Mutex<HashMap<NamespaceName, CancellationToken>>>,
+}
+
+impl NamespaceTokens {
+    /// Returns the child token for `namespace`, deriving one from `root` if this is the first
+    /// time this namespace is seen.
+    pub(super) fn get_or_insert(&self, root: &CancellationToken, namespace: &NamespaceName) -> CancellationToken {
+        let mut tokens = self.tokens.lock().unwrap();
+        tokens
+            .entry(namespace.clone())
+            .or_insert_with(|| root.child_token())
+            .clone()
+    }
+
+    /// Cancels the in-flight work for a single namespace, if any is tracked.
+    pub(super) fn cancel(&self, namespace: &NamespaceName) {
+        if let Some(token) = self.tokens.lock().unwrap().remove(namespace) {
+            token.cancel();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_propagates_to_children_but_not_siblings() {
+        let root = CancellationToken::new();
+        let child_a = root.child_token();
+        let child_b = root.child_token();
+        let grandchild = child_a.child_token();
+
+        child_a.cancel();
+
+        assert!(child_a.is_cancelled());
+        assert!(grandchild.is_cancelled());
+        assert!(!child_b.is_cancelled());
+        assert!(!root.is_cancelled());
+    }
+
+    #[test]
+    fn cancel_on_root_propagates_to_all_descendants() {
+        let root = CancellationToken::new();
+        let child = root.child_token();
+        let grandchild = child.child_token();
+
+        root.cancel();
+
+        assert!(root.is_cancelled());
+        assert!(child.is_cancelled());
+        assert!(grandchild.is_cancelled());
+    }
+
+    #[test]
+    fn child_token_created_after_parent_cancelled_is_already_cancelled() {
+        let root = CancellationToken::new();
+        root.cancel();
+
+        let child = root.child_token();
+
+        assert!(child.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn cancelled_resolves_immediately_once_cancelled() {
+        let token = CancellationToken::new();
+        token.cancel();
+
+        // must not hang: `cancelled()` checks the flag before waiting on `Notify`.
+        token.cancelled().await;
+    }
+
+    #[tokio::test]
+    async fn cancelled_wakes_a_pending_waiter() {
+        let token = CancellationToken::new();
+        let waiter_token = token.clone();
+
+        let waiter = tokio::spawn(async move {
+            waiter_token.cancelled().await;
+        });
+
+        // give the spawned task a chance to start waiting before we cancel.
+        tokio::task::yield_now().await;
+        token.cancel();
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), waiter)
+            .await
+            .expect("cancelled() waiter was not woken in time")
+            .unwrap();
+    }
+
+    #[test]
+    fn namespace_tokens_get_or_insert_is_stable_per_namespace() {
+        let root = CancellationToken::new();
+        let tokens = NamespaceTokens::default();
+        let ns = NamespaceName::from_string("ns".into());
+
+        let a = tokens.get_or_insert(&root, &ns);
+        let b = tokens.get_or_insert(&root, &ns);
+
+        a.cancel();
+        assert!(b.is_cancelled(), "get_or_insert must return the same token for a namespace");
+    }
+
+    #[test]
+    fn namespace_tokens_cancel_is_scoped_to_one_namespace() {
+        let root = CancellationToken::new();
+        let tokens = NamespaceTokens::default();
+        let ns_a = NamespaceName::from_string("a".into());
+        let ns_b = NamespaceName::from_string("b".into());
+
+        let token_a = tokens.get_or_insert(&root, &ns_a);
+        let token_b = tokens.get_or_insert(&root, &ns_b);
+
+        tokens.cancel(&ns_a);
+
+        assert!(token_a.is_cancelled());
+        assert!(!token_b.is_cancelled());
+        assert!(!root.is_cancelled());
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tokio::sync::Notify;
+
+use libsql_sys::name::NamespaceName;
+
+/// Tracks, per namespace, the highest frame_no known to be durable, and wakes any waiter
+/// blocked on [`wait_for`](Self::wait_for) once the threshold it's waiting for is reached.
+///
+/// Shared between [`Bottomless`](super::Bottomless) and its [`BottomlessLoop`](super::BottomlessLoop),
+/// so that `Bottomless::store` can wait for its own request to become durable without having
+/// to consume a dedicated channel message per caller.
+#[derive(Default)]
+pub(super) struct DurableIndexTracker {
+    durable: Mutex<HashMap<NamespaceName, u64>>,
+    notify: Notify,
+}
+
+impl DurableIndexTracker {
+    /// Records that `frame_no` is now durable for `namespace`, waking any waiter that might
+    /// now be satisfied. A no-op if `frame_no` is not an advancement.
+    pub(super) fn update(&self, namespace: NamespaceName, frame_no: u64) {
+        {
+            let mut durable = self.durable.lock().unwrap();
+            let current = durable.entry(namespace).or_insert(0);
+            if frame_no <= *current {
+                return;
+            }
+            *current = frame_no;
+        }
+
+        self.notify.notify_waiters();
+    }
+
+    /// Resolves once `namespace`'s durable frame_no is `>= frame_no`, returning the durable
+    /// frame_no observed (which may be higher than requested), immediately if already durable.
+    pub(super) async fn wait_for(&self, namespace: &NamespaceName, frame_no: u64) -> u64 {
+        loop {
+            let notified = self.notify.notified();
+            if let Some(durable) = self.current(namespace) {
+                if durable >= frame_no {
+                    return durable;
+                }
+            }
+            notified.await;
+        }
+    }
+
+    fn current(&self, namespace: &NamespaceName) -> Option<u64> {
+        self.durable.lock().unwrap().get(namespace).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn wait_for_resolves_immediately_if_already_durable() {
+        let tracker = DurableIndexTracker::default();
+        let ns = NamespaceName::from_string("ns".into());
+
+        tracker.update(ns.clone(), 10);
+
+        // must not hang: `wait_for` checks the current state before waiting on `Notify`.
+        let durable = tracker.wait_for(&ns, 5).await;
+        assert_eq!(durable, 10);
+    }
+
+    #[tokio::test]
+    async fn wait_for_wakes_once_the_threshold_is_reached() {
+        let tracker = Arc::new(DurableIndexTracker::default());
+        let ns = NamespaceName::from_string("ns".into());
+
+        let waiter = tokio::spawn({
+            let tracker = tracker.clone();
+            let ns = ns.clone();
+            async move { tracker.wait_for(&ns, 10).await }
+        });
+
+        tokio::task::yield_now().await;
+        tracker.update(ns.clone(), 5);
+        tokio::task::yield_now().await;
+        tracker.update(ns.clone(), 10);
+
+        let durable = tokio::time::timeout(std::time::Duration::from_secs(5), waiter)
+            .await
+            .expect("wait_for was not woken in time")
+            .unwrap();
+        assert_eq!(durable, 10);
+    }
+
+    #[tokio::test]
+    async fn update_is_a_noop_for_a_lower_or_equal_frame_no() {
+        let tracker = DurableIndexTracker::default();
+        let ns = NamespaceName::from_string("ns".into());
+
+        tracker.update(ns.clone(), 10);
+        tracker.update(ns.clone(), 3);
+
+        assert_eq!(tracker.current(&ns), Some(10));
+    }
+
+    #[tokio::test]
+    async fn namespaces_are_tracked_independently() {
+        let tracker = DurableIndexTracker::default();
+        let ns_a = NamespaceName::from_string("a".into());
+        let ns_b = NamespaceName::from_string("b".into());
+
+        tracker.update(ns_a.clone(), 10);
+
+        assert_eq!(tracker.current(&ns_a), Some(10));
+        assert_eq!(tracker.current(&ns_b), None);
+    }
+}
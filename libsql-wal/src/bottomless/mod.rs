@@ -3,17 +3,21 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{mpsc, Semaphore};
 use tokio::task::{JoinHandle, JoinSet};
 
 use crate::io::Io;
 use crate::segment::sealed::SealedSegment;
 use libsql_sys::name::NamespaceName;
 
+use self::cancellation::{CancellationToken, NamespaceTokens};
+use self::durability::DurableIndexTracker;
 use self::job::JobResult;
 use self::scheduler::Scheduler;
 use self::storage::Storage;
 
+mod cancellation;
+mod durability;
 mod job;
 mod restore;
 mod scheduler;
@@ -32,7 +36,14 @@ pub struct BottomlessLoop<S: Storage, FS: Io> {
     filesystem: Arc<FS>,
     max_in_flight: usize,
     in_flight_futs: JoinSet<JobResult<S::Config, Arc<SealedSegment<FS::File>>>>,
-    force_shutdown: oneshot::Receiver<()>,
+    /// root of the cancellation token tree. Cancelling it tears down the whole loop.
+    root: CancellationToken,
+    /// per-namespace child tokens, so that a single namespace's in-flight jobs can be
+    /// cancelled without affecting the others.
+    namespace_tokens: NamespaceTokens,
+    /// tracks the highest durable frame_no per namespace, shared with `Bottomless` so that
+    /// `store` can wait for its own request to become durable.
+    durable_index: Arc<DurableIndexTracker>,
 }
 
 impl<S, FS> BottomlessLoop<S, FS>
@@ -47,7 +58,8 @@ where
     /// with it.
     ///
     /// The loop is only allowed to shutdown if the receiver is closed, and the scheduler is empty,
-    /// or if `force_shutdown` is called, in which case everything is dropped in place.
+    /// or if the root cancellation token is cancelled, in which case everything is dropped in
+    /// place.
     #[tracing::instrument(skip(self))]
     async fn run(mut self) {
         let mut shutting_down = false;
@@ -63,8 +75,17 @@ where
                     .scheduler
                     .schedule()
                     .expect("scheduler has work, but didn't return a job");
-                self.in_flight_futs
-                    .spawn(job.perform(self.storage.clone(), self.filesystem.clone()));
+                let namespace_token = self
+                    .namespace_tokens
+                    .get_or_insert(&self.root, job.namespace());
+                let fut = job.perform(self.storage.clone(), self.filesystem.clone());
+                self.in_flight_futs.spawn(async move {
+                    tokio::select! {
+                        biased;
+                        _ = namespace_token.cancelled() => JobResult::cancelled(),
+                        result = fut => result,
+                    }
+                });
             }
 
             tokio::select! {
@@ -76,6 +97,15 @@ where
                             if shutting_down {
                                 tracing::info!("processed job, {} jobs remaining", self.in_flight_futs.len());
                             }
+                            // a cancelled job never actually made its segment durable: treating
+                            // it as such would falsely unblock a `Bottomless::store` waiter for
+                            // data that was, in fact, never persisted.
+                            if !job_result.is_cancelled() {
+                                self.durable_index.update(
+                                    job_result.namespace().clone(),
+                                    job_result.durable_frame_no(),
+                                );
+                            }
                             self.scheduler.report(job_result).await;
                         }
                         Err(e) => {
@@ -96,14 +126,8 @@ where
                         }
                     }
                 }
-                shutdown = &mut self.force_shutdown => {
-                    if shutdown.is_ok() {
-                        break
-                    } else {
-                        // force_shutdown sender was dropped without sending a message (likely a
-                        // bug). Log and default to graceful shutdown.
-                        // tracing::error!("bottomless force shutdown handle dropped without notifying; shutting down gracefully");
-                    }
+                _ = self.root.cancelled() => {
+                    break
                 }
             }
         }
@@ -118,14 +142,32 @@ pub struct BottomlessConfig<C> {
     config: C,
 }
 
+impl<C> BottomlessConfig<C> {
+    pub fn new(max_jobs_conccurency: usize, max_enqueued_jobs: usize, config: C) -> Self {
+        Self {
+            max_jobs_conccurency,
+            max_enqueued_jobs,
+            config,
+        }
+    }
+}
+
 pub struct Bottomless<C, S> {
     /// send request to the main loop
     job_sender: mpsc::Sender<StoreSegmentRequest<C, S>>,
-    /// receiver for the current max durable index
-    durable_notifier: mpsc::Receiver<(NamespaceName, u64)>,
+    /// bounds the number of store jobs enqueued but not yet durable, to
+    /// `BottomlessConfig::max_enqueued_jobs`, throttling producers that outpace the
+    /// durability loop instead of growing the queue without bound.
+    enqueue_semaphore: Arc<Semaphore>,
+    /// tracks the highest durable frame_no per namespace, shared with the `BottomlessLoop`.
+    durable_index: Arc<DurableIndexTracker>,
     /// join handle to the `BottomlessLoop`
     loop_handle: JoinHandle<()>,
-    force_shutdown: oneshot::Sender<()>,
+    /// root of the cancellation token tree shared with the `BottomlessLoop`. Cancelling it
+    /// forcefully tears down the loop; deriving a child per namespace lets a single
+    /// namespace's in-flight work be aborted instead.
+    root: CancellationToken,
+    namespace_tokens: NamespaceTokens,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -136,43 +178,103 @@ pub enum Error {
     Store(String),
     #[error("unable to find the requested frame_no: {0}")]
     FrameNotFound(u64),
+    #[error("checksum mismatch: expected {expected:08x}, got {got:08x}")]
+    ChecksumMismatch { expected: u32, got: u32 },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
-impl<C, F> Bottomless<C, F> {
-    pub async fn new<S: Storage>(_storage: S) -> Result<Bottomless<S::Config, F>> {
-        todo!()
+impl<S, FS> Bottomless<S::Config, Arc<SealedSegment<FS::File>>>
+where
+    S: Storage + 'static,
+    FS: Io,
+{
+    /// Spawns the `BottomlessLoop` background task and returns a handle to enqueue durability
+    /// requests against it. `config.max_enqueued_jobs` sizes both `enqueue_semaphore` (how many
+    /// requests `store` admits before throttling producers) and the request channel itself;
+    /// `config.max_jobs_conccurency` bounds how many of those the loop processes at once.
+    pub fn new(storage: Arc<S>, filesystem: Arc<FS>, config: BottomlessConfig<S::Config>) -> Self {
+        let (job_sender, receiver) = mpsc::channel(config.max_enqueued_jobs);
+        let enqueue_semaphore = Arc::new(Semaphore::new(config.max_enqueued_jobs));
+        let durable_index = Arc::new(DurableIndexTracker::default());
+        let root = CancellationToken::new();
+        let namespace_tokens = NamespaceTokens::default();
+
+        let bottomless_loop = BottomlessLoop {
+            receiver,
+            scheduler: Scheduler::new(),
+            storage,
+            filesystem,
+            max_in_flight: config.max_jobs_conccurency,
+            in_flight_futs: JoinSet::new(),
+            root: root.clone(),
+            namespace_tokens: namespace_tokens.clone(),
+            durable_index: durable_index.clone(),
+        };
+        let loop_handle = tokio::spawn(bottomless_loop.run());
+
+        Self {
+            job_sender,
+            enqueue_semaphore,
+            durable_index,
+            loop_handle,
+            root,
+            namespace_tokens,
+        }
     }
-    /// Send a request make a segment durable. Return a future that resolves when that segment
-    /// becomes durable.
-    pub async fn store(&self, _request: StoreSegmentRequest<C, F>) {
+}
+
+impl<C, F> Bottomless<C, F> {
+    /// Send a request make a segment durable. Returns the durable frame_no once the request's
+    /// `end_frame_no` has been made durable, throttling the caller if too many jobs are already
+    /// enqueued and not yet durable.
+    pub async fn store(&self, request: StoreSegmentRequest<C, F>) -> u64 {
         assert!(
             !self.job_sender.is_closed(),
             "bottomless loop was closed before the handle was dropped"
         );
-        todo!();
+
+        let _permit = self
+            .enqueue_semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("enqueue semaphore is never closed while `Bottomless` is alive");
+
+        let namespace = request.namespace.clone();
+        let end_frame_no = request.end_frame_no;
+
+        self.job_sender
+            .send(request)
+            .await
+            .expect("bottomless loop was closed before the handle was dropped");
+
+        self.durable_index.wait_for(&namespace, end_frame_no).await
+    }
+
+    /// Cancels the in-flight durability work of a single namespace (e.g. because its database
+    /// is being dropped or migrated), without affecting any other namespace or the loop itself.
+    pub fn cancel_namespace(&self, namespace: &NamespaceName) {
+        self.namespace_tokens.cancel(namespace);
     }
 
     /// Tries to shutdown bottomless gracefully.
     /// If timeout expires, bottomless is forcefully shutdown.
     pub async fn shutdown(self, timeout: Duration) {
-        let (mut handle, force_shutdown) = {
+        let (mut handle, root) = {
             // we drop the sender, the loop will finish processing scheduled job and exit
             // gracefully.
             let Self {
-                loop_handle,
-                force_shutdown,
-                ..
+                loop_handle, root, ..
             } = self;
-            (loop_handle, force_shutdown)
+            (loop_handle, root)
         };
 
         match tokio::time::timeout(timeout, &mut handle).await {
             Ok(_) => (),
             Err(_) => {
                 tracing::error!("Bottomless graceful shutdown elapsed, shutting down forcefully");
-                let _ = force_shutdown.send(());
+                root.cancel();
                 handle
                     .await
                     .expect("bottomless loop panicked while shutting down");
@@ -191,4 +293,6 @@ pub struct StoreSegmentRequest<C, T> {
     /// alternative configuration to use with the storage layer.
     /// e.g: S3 overrides
     storage_config_override: Option<Arc<C>>,
+    /// the frame_no that must be durable for this request to be considered complete
+    end_frame_no: u64,
 }